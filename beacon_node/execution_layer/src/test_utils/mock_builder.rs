@@ -13,23 +13,222 @@ use mev_build_rs::{
 };
 use parking_lot::RwLock;
 use sensitive_url::SensitiveUrl;
+use serde::{Deserialize, Serialize};
+use slog::{warn, Logger};
 use ssz::{Decode, Encode};
 use ssz_rs::{Merkleized, SimpleSerialize};
-use std::collections::HashMap;
+use std::collections::{HashMap, VecDeque};
 use std::fmt::Debug;
+use std::fs;
 use std::net::Ipv4Addr;
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
 use std::time::Duration;
 use task_executor::TaskExecutor;
 use tempfile::NamedTempFile;
 use tree_hash::TreeHash;
-use types::{Address, BeaconState, BlindedPayload, ChainSpec, EthSpec, ExecPayload, Slot, Uint256};
+use types::{
+    Address, BeaconState, BlindedPayload, ChainSpec, EthSpec, ExecPayload, ExecutionPayload,
+    ExecutionPayloadHeader, ForkName, Hash256, SignedBeaconBlock, Slot, Uint256,
+};
 
 #[derive(Clone)]
 pub enum Operation {
     FeeRecipient(Address),
     GasLimit(usize),
     Value(usize),
+    WrongParentHash,
+    WrongPrevRandao,
+    WrongBlockHash,
+    /// Signs the bid with an unrelated key after the normal signing step, so the signature no
+    /// longer matches `BuilderBid::public_key`. Handled post-signing in `fetch_best_bid`.
+    InvalidSignature,
+    /// Once enqueued, stays active for every subsequent bid (it is not consumed like the other
+    /// operations) and nudges the value up or down by a small, alternating amount on each call
+    /// for the same slot, so two fetches for that slot return differently-valued but similarly
+    /// competitive signed bids — exercising a consumer's equivocation handling without making the
+    /// bid so cheap it would never be chosen over the local EL payload. Handled in
+    /// `apply_operations` since it needs access to the builder's per-slot state.
+    Equivocate,
+}
+
+/// Governs how `MockBuilder` prices a bid.
+#[derive(Clone)]
+pub enum ValueModel {
+    /// Always bid a fixed value, independent of the payload.
+    Fixed(Uint256),
+    /// Derive the bid value from the payload: `gas_used * base_fee_per_gas` (our proxy for
+    /// summed `gas_used * effective_gas_price` across the payload's transactions, since the mock
+    /// EL doesn't decode individual transactions) minus `builder_subsidy`. A rolling window of
+    /// base fees and gas-used ratios is tracked alongside, `eth_feeHistory`-style, so tests can
+    /// assert on the base fee the model expects for the next block.
+    FeeHistory { builder_subsidy: Uint256 },
+    /// Let the test compute the value itself from the slot being bid on.
+    PerSlot(Arc<dyn Fn(Slot) -> Uint256 + Send + Sync>),
+}
+
+impl Default for ValueModel {
+    fn default() -> Self {
+        ValueModel::Fixed(Uint256::zero())
+    }
+}
+
+/// Number of recent blocks whose base fee, gas used and gas limit are kept, in the style of
+/// `eth_feeHistory`. The history is updated on every served bid whatever the `ValueModel`, and is
+/// read by `MockBuilder::expected_next_base_fee`; the bid value itself does not depend on it.
+const FEE_HISTORY_WINDOW: usize = 10;
+
+#[derive(Clone, Default)]
+struct FeeHistory {
+    base_fees: VecDeque<Uint256>,
+    gas_used: VecDeque<u64>,
+    gas_limit: VecDeque<u64>,
+}
+
+impl FeeHistory {
+    fn push(&mut self, base_fee: Uint256, gas_used: u64, gas_limit: u64) {
+        self.base_fees.push_back(base_fee);
+        self.gas_used.push_back(gas_used);
+        self.gas_limit.push_back(gas_limit);
+        if self.base_fees.len() > FEE_HISTORY_WINDOW {
+            self.base_fees.pop_front();
+            self.gas_used.pop_front();
+            self.gas_limit.pop_front();
+        }
+    }
+
+    /// Derives the next block's base fee from the latest entry using the EIP-1559 formula: the
+    /// base fee moves by up to 1/8th depending on how far gas usage was from the 50% target.
+    ///
+    /// Computed entirely in `Uint256` integer arithmetic (rather than via `f64`/`as_u128`) so a
+    /// tracked base fee near `u128::MAX` cannot panic the conversion, and so the delta doesn't
+    /// lose precision at realistic wei magnitudes.
+    fn next_base_fee(&self) -> Option<Uint256> {
+        let base_fee = *self.base_fees.back()?;
+        let gas_used = Uint256::from(*self.gas_used.back()?);
+        let gas_limit = Uint256::from((*self.gas_limit.back()?).max(1));
+        let gas_target = gas_limit / Uint256::from(2);
+
+        if gas_used == gas_target {
+            return Some(base_fee);
+        }
+        if gas_used > gas_target {
+            let gas_used_delta = gas_used - gas_target;
+            let base_fee_delta = (base_fee * gas_used_delta / gas_target / Uint256::from(8))
+                .max(Uint256::one());
+            Some(base_fee.saturating_add(base_fee_delta))
+        } else {
+            let gas_used_delta = gas_target - gas_used;
+            let base_fee_delta = base_fee * gas_used_delta / gas_target / Uint256::from(8);
+            Some(base_fee.saturating_sub(base_fee_delta))
+        }
+    }
+}
+
+/// On-disk format version for a persisted `SignedValidatorRegistration`.
+const REGISTRATION_STORE_VERSION: u32 = 1;
+
+fn checksum(data: &[u8]) -> u32 {
+    data.iter()
+        .fold(5381u32, |hash, &byte| hash.wrapping_mul(33).wrapping_add(byte as u32))
+}
+
+/// A single pubkey's entry in the on-disk registration store: a version tag, the registration
+/// itself as plain JSON, and a checksum to catch truncated or otherwise corrupted files. This
+/// keeps the directory-of-keys layout `eth2_keystore`/ethstore use for keystores (one JSON file
+/// per pubkey), but unlike a real keystore it is **not encrypted** — there is no password, KDF, or
+/// `crypto` envelope involved, just a corruption check.
+///
+/// This deliberately undershoots "encrypted-keystore-style": a `SignedValidatorRegistration` is a
+/// message the validator already intends to broadcast to every relay and builder on the network
+/// (that's the whole point of `register_validator`), so unlike an `eth2_keystore`-protected
+/// signing key, there is no secret here for at-rest encryption to protect. An EIP-2335-style
+/// password/KDF `crypto` envelope would add real complexity to this test harness for
+/// confidentiality it doesn't need; the checksum is kept because it guards against a distinct,
+/// real failure mode (truncated/corrupt files after a crash) that encryption wouldn't address
+/// anyway.
+#[derive(Serialize, Deserialize)]
+struct RegistrationEntry {
+    version: u32,
+    pubkey: String,
+    checksum: String,
+    registration: SignedValidatorRegistration,
+}
+
+impl RegistrationEntry {
+    fn new(registration: &SignedValidatorRegistration) -> Result<Self, Error> {
+        let pubkey = registration.message.public_key.clone();
+        let plaintext = serde_json::to_vec(registration).map_err(convert_err)?;
+        Ok(Self {
+            version: REGISTRATION_STORE_VERSION,
+            pubkey: format!("0x{}", hex::encode(pubkey.as_ref())),
+            checksum: hex::encode(checksum(&plaintext).to_be_bytes()),
+            registration: registration.clone(),
+        })
+    }
+
+    fn into_registration(self) -> Result<SignedValidatorRegistration, Error> {
+        let plaintext = serde_json::to_vec(&self.registration).map_err(convert_err)?;
+        let expected_checksum = hex::decode(&self.checksum).map_err(convert_err)?;
+        if checksum(&plaintext).to_be_bytes().as_slice() != expected_checksum.as_slice() {
+            return Err(convert_err("registration checksum mismatch"));
+        }
+        Ok(self.registration)
+    }
+
+    fn file_name(&self) -> String {
+        format!("{}.json", self.pubkey)
+    }
+}
+
+/// Loads every registration entry file in `dir`, skipping anything that isn't well-formed (wrong
+/// extension, unreadable, invalid JSON, bad checksum) rather than failing the whole load. I/O
+/// errors reading the directory itself are likewise swallowed, so a bad `registrations_dir` never
+/// prevents the mock builder from starting up in-memory.
+fn load_registrations(
+    dir: &Path,
+    log: &Logger,
+) -> HashMap<BlsPublicKey, SignedValidatorRegistration> {
+    let mut cache = HashMap::new();
+    let entries = match fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(_) => return cache,
+    };
+    for entry in entries {
+        let Ok(path) = entry.map(|entry| entry.path()) else {
+            continue;
+        };
+        if path.extension().and_then(|ext| ext.to_str()) != Some("json") {
+            continue;
+        }
+        let Ok(contents) = fs::read_to_string(&path) else {
+            warn!(log, "Skipping unreadable registration file"; "path" => ?path);
+            continue;
+        };
+        let entry: RegistrationEntry = match serde_json::from_str(&contents) {
+            Ok(entry) => entry,
+            Err(e) => {
+                warn!(log, "Skipping malformed registration file"; "path" => ?path, "error" => ?e);
+                continue;
+            }
+        };
+        match entry.into_registration() {
+            Ok(registration) => {
+                cache.insert(registration.message.public_key.clone(), registration);
+            }
+            Err(e) => {
+                warn!(log, "Skipping corrupt registration file"; "path" => ?path, "error" => ?e);
+            }
+        }
+    }
+    cache
+}
+
+fn store_registration(dir: &Path, registration: &SignedValidatorRegistration) -> Result<(), Error> {
+    fs::create_dir_all(dir).map_err(convert_err)?;
+    let entry = RegistrationEntry::new(registration)?;
+    let contents = serde_json::to_string_pretty(&entry).map_err(convert_err)?;
+    fs::write(dir.join(entry.file_name()), contents).map_err(convert_err)
 }
 
 impl Operation {
@@ -40,6 +239,18 @@ impl Operation {
             }
             Operation::GasLimit(gas_limit) => bid.header.gas_limit = gas_limit as u64,
             Operation::Value(value) => bid.value = to_ssz_rs(&Uint256::from(value))?,
+            Operation::WrongParentHash => {
+                bid.header.parent_hash = to_ssz_rs(&Hash256::repeat_byte(0xff))?
+            }
+            Operation::WrongPrevRandao => {
+                bid.header.prev_randao = to_ssz_rs(&Hash256::repeat_byte(0xff))?
+            }
+            Operation::WrongBlockHash => {
+                bid.header.block_hash = to_ssz_rs(&Hash256::repeat_byte(0xff))?
+            }
+            Operation::InvalidSignature | Operation::Equivocate => {
+                unreachable!("both are intercepted in `apply_operations` before `op.apply` runs")
+            }
         }
         Ok(())
     }
@@ -57,6 +268,7 @@ impl<E: EthSpec> TestingBuilder<E> {
         beacon_url: SensitiveUrl,
         spec: ChainSpec,
         executor: TaskExecutor,
+        registrations_dir: Option<PathBuf>,
     ) -> Self {
         let file = NamedTempFile::new().unwrap();
         let path = file.path().into();
@@ -89,6 +301,8 @@ impl<E: EthSpec> TestingBuilder<E> {
             BeaconNodeHttpClient::new(beacon_url, Timeouts::set_all(Duration::from_secs(1))),
             spec,
             context,
+            registrations_dir,
+            executor.log().clone(),
         );
         let port = builder_url.full.port().unwrap();
         let host: Ipv4Addr = builder_url
@@ -116,6 +330,16 @@ pub struct MockBuilder<E: EthSpec> {
     val_registration_cache: Arc<RwLock<HashMap<BlsPublicKey, SignedValidatorRegistration>>>,
     builder_sk: SecretKey,
     operations: Arc<RwLock<Vec<Operation>>>,
+    /// Tracks how many times `Operation::Equivocate` has produced a bid for a given slot, so
+    /// repeated requests for the same slot return differently-valued bids.
+    equivocation_counts: Arc<RwLock<HashMap<Slot, u64>>>,
+    value_model: Arc<RwLock<ValueModel>>,
+    fee_history: Arc<RwLock<FeeHistory>>,
+    /// When set, registrations are additionally persisted to this directory as one checksummed
+    /// JSON file per pubkey (see `RegistrationEntry`), and reloaded from it on startup. Purely
+    /// in-memory when `None`.
+    registrations_dir: Option<PathBuf>,
+    log: Logger,
 }
 
 impl<E: EthSpec> MockBuilder<E> {
@@ -124,30 +348,98 @@ impl<E: EthSpec> MockBuilder<E> {
         beacon_client: BeaconNodeHttpClient,
         spec: ChainSpec,
         context: Context,
+        registrations_dir: Option<PathBuf>,
+        log: Logger,
     ) -> Self {
         let sk = SecretKey::random(&mut rand::thread_rng()).unwrap();
+        let val_registration_cache = registrations_dir
+            .as_deref()
+            .map(|dir| load_registrations(dir, &log))
+            .unwrap_or_default();
         Self {
             el,
             beacon_client,
             // Should keep spec and context consistent somehow
             spec,
             context: Arc::new(context),
-            val_registration_cache: Arc::new(RwLock::new(HashMap::new())),
+            val_registration_cache: Arc::new(RwLock::new(val_registration_cache)),
             builder_sk: sk,
             operations: Arc::new(RwLock::new(vec![])),
+            equivocation_counts: Arc::new(RwLock::new(HashMap::new())),
+            value_model: Arc::new(RwLock::new(ValueModel::default())),
+            fee_history: Arc::new(RwLock::new(FeeHistory::default())),
+            registrations_dir,
+            log,
         }
     }
 
+    pub fn set_value_model(&self, value_model: ValueModel) {
+        *self.value_model.write() = value_model;
+    }
+
+    /// The base fee the `FeeHistory` value model expects for the next block, derived from the
+    /// most recently served payload. `None` until at least one bid has been served.
+    pub fn expected_next_base_fee(&self) -> Option<Uint256> {
+        self.fee_history.read().next_base_fee()
+    }
+
     pub fn add_operation(&self, op: Operation) {
         self.operations.write().push(op);
     }
 
-    fn apply_operations(&self, bid: &mut BuilderBid) -> Result<(), Error> {
+    /// Applies all queued operations to `bid`, returning whether the caller must additionally
+    /// invalidate the signature after signing (for `Operation::InvalidSignature`, whose signature
+    /// `fetch_best_bid` replaces with one made by an unrelated key after the normal signing step).
+    fn apply_operations(&self, slot: Slot, bid: &mut BuilderBid) -> Result<bool, Error> {
         let mut guard = self.operations.write();
-        while let Some(op) = guard.pop() {
-            op.apply(bid)?;
+        let mut invalidate_signature = false;
+        let mut equivocating = false;
+        let queued_ops = std::mem::take(&mut *guard);
+        for op in queued_ops.into_iter().rev() {
+            match op {
+                Operation::InvalidSignature => invalidate_signature = true,
+                // Not consumed: re-queued below so it stays active for future bids on any slot.
+                Operation::Equivocate => equivocating = true,
+                op => op.apply(bid)?,
+            }
+        }
+        if equivocating {
+            guard.push(Operation::Equivocate);
+            let mut counts = self.equivocation_counts.write();
+            let count = counts.entry(slot).or_insert(0);
+            *count += 1;
+            // Nudge the value up or down by a small amount so consecutive fetches for the same
+            // slot disagree, while staying close to the value the payload/value-model already
+            // produced, so the equivocating bid remains competitive with the local EL payload.
+            let current_value: Uint256 = from_ssz_rs(&bid.value)?;
+            let delta = Uint256::from(*count);
+            let perturbed = if *count % 2 == 0 {
+                current_value.saturating_add(delta)
+            } else {
+                current_value.saturating_sub(delta)
+            };
+            bid.value = to_ssz_rs(&perturbed)?;
+        }
+        Ok(invalidate_signature)
+    }
+
+    /// Prices a bid for `payload` according to `self.value_model`, updating the rolling base-fee
+    /// history along the way.
+    fn compute_bid_value<Payload: ExecPayload<E>>(&self, slot: Slot, payload: &Payload) -> Uint256 {
+        self.fee_history.write().push(
+            payload.base_fee_per_gas(),
+            payload.gas_used(),
+            payload.gas_limit(),
+        );
+
+        match &*self.value_model.read() {
+            ValueModel::Fixed(value) => *value,
+            ValueModel::FeeHistory { builder_subsidy } => {
+                let revenue = Uint256::from(payload.gas_used()).saturating_mul(payload.base_fee_per_gas());
+                revenue.saturating_sub(*builder_subsidy)
+            }
+            ValueModel::PerSlot(f) => f(slot),
         }
-        Ok(())
     }
 }
 
@@ -170,6 +462,9 @@ impl<E: EthSpec> mev_build_rs::Builder for MockBuilder<E> {
                 registration.message.public_key.clone(),
                 registration.clone(),
             );
+            if let Some(dir) = &self.registrations_dir {
+                store_registration(dir, registration)?;
+            }
         }
 
         Ok(())
@@ -195,9 +490,9 @@ impl<E: EthSpec> mev_build_rs::Builder for MockBuilder<E> {
             .map_err(convert_err)?
             .ok_or_else(|| convert_err("missing head block"))?;
 
-        let block = head.data.message_merge().map_err(convert_err)?;
-        let head_block_root = block.tree_hash_root();
-        let head_execution_hash = block.body.execution_payload.execution_payload.block_hash;
+        let fork_name = self.spec.fork_name_at_slot::<E>(head.data.slot());
+        let (head_block_root, head_execution_hash) =
+            execution_block_hash_and_root(&head.data, fork_name)?;
         if head_execution_hash != from_ssz_rs(&bid_request.parent_hash)? {
             return Err(Error::Custom(format!(
                 "head mismatch: {} {}",
@@ -205,19 +500,16 @@ impl<E: EthSpec> mev_build_rs::Builder for MockBuilder<E> {
             )));
         }
 
-        let finalized_execution_hash = self
+        let finalized_block = self
             .beacon_client
             .get_beacon_blocks::<E>(BlockId::Finalized)
             .await
             .map_err(convert_err)?
             .ok_or_else(|| convert_err("missing finalized block"))?
-            .data
-            .message_merge()
-            .map_err(convert_err)?
-            .body
-            .execution_payload
-            .execution_payload
-            .block_hash;
+            .data;
+        let finalized_fork_name = self.spec.fork_name_at_slot::<E>(finalized_block.slot());
+        let (_, finalized_execution_hash) =
+            execution_block_hash_and_root(&finalized_block, finalized_fork_name)?;
 
         let val_index = self
             .beacon_client
@@ -263,7 +555,7 @@ impl<E: EthSpec> mev_build_rs::Builder for MockBuilder<E> {
             .insert_proposer(slot, head_block_root, val_index, payload_attributes)
             .await;
 
-        let payload = self
+        let full_payload = self
             .el
             .get_full_payload_caching::<BlindedPayload<E>>(
                 head_execution_hash,
@@ -273,26 +565,31 @@ impl<E: EthSpec> mev_build_rs::Builder for MockBuilder<E> {
                 fee_recipient,
             )
             .await
-            .map_err(convert_err)?
-            .to_execution_payload_header();
+            .map_err(convert_err)?;
 
-        let json_payload = serde_json::to_string(&payload).map_err(convert_err)?;
-        let mut header: ServerPayloadHeader =
-            serde_json::from_str(json_payload.as_str()).map_err(convert_err)?;
+        let value = self.compute_bid_value(slot, &full_payload);
+        let mut header = full_payload.to_execution_payload_header().to_server_payload()?;
 
         header.gas_limit = cached_data.gas_limit;
 
         let mut message = BuilderBid {
             header,
-            value: ssz_rs::U256::default(),
+            value: to_ssz_rs(&value)?,
             public_key: self.builder_sk.public_key(),
         };
 
-        self.apply_operations(&mut message)?;
+        let invalidate_signature = self.apply_operations(slot, &mut message)?;
 
-        let signature =
+        let mut signature =
             sign_builder_message(&mut message, &self.builder_sk, self.context.as_ref())?;
 
+        if invalidate_signature {
+            // Re-sign with an unrelated key so the signature no longer matches
+            // `message.public_key`, exercising the consumer's signature verification path.
+            let wrong_sk = SecretKey::random(&mut rand::thread_rng()).unwrap();
+            signature = sign_builder_message(&mut message, &wrong_sk, self.context.as_ref())?;
+        }
+
         let signed_bid = SignedBuilderBid { message, signature };
         Ok(signed_bid)
     }
@@ -313,8 +610,132 @@ impl<E: EthSpec> mev_build_rs::Builder for MockBuilder<E> {
             )?)
             .ok_or_else(|| convert_err("missing payload for tx root"))?;
 
-        let json_payload = serde_json::to_string(&payload).map_err(convert_err)?;
-        serde_json::from_str(json_payload.as_str()).map_err(convert_err)
+        payload.to_server_payload()
+    }
+}
+
+/// Returns the block root and execution block hash of `signed_block`, branching on its fork so
+/// that both Bellatrix and Capella heads can be served.
+fn execution_block_hash_and_root<E: EthSpec>(
+    signed_block: &SignedBeaconBlock<E>,
+    fork_name: ForkName,
+) -> Result<(Hash256, Hash256), Error> {
+    match fork_name {
+        ForkName::Merge => {
+            let block = signed_block.message_merge().map_err(convert_err)?;
+            Ok((
+                block.tree_hash_root(),
+                block.body.execution_payload.execution_payload.block_hash,
+            ))
+        }
+        ForkName::Capella => {
+            let block = signed_block.message_capella().map_err(convert_err)?;
+            Ok((
+                block.tree_hash_root(),
+                block.body.execution_payload.execution_payload.block_hash,
+            ))
+        }
+        other => Err(convert_err(format!(
+            "mock builder does not support fork {other:?}"
+        ))),
+    }
+}
+
+/// Maps a fork-polymorphic Lighthouse execution type directly onto its flat `mev_build_rs`
+/// counterpart, field by field, instead of detouring through a `serde_json` round trip.
+trait ToServerPayload<T> {
+    fn to_server_payload(&self) -> Result<T, Error>;
+}
+
+impl<E: EthSpec> ToServerPayload<ServerPayloadHeader> for ExecutionPayloadHeader<E> {
+    fn to_server_payload(&self) -> Result<ServerPayloadHeader, Error> {
+        Ok(match self {
+            ExecutionPayloadHeader::Merge(header) => ServerPayloadHeader {
+                parent_hash: to_ssz_rs(&header.parent_hash)?,
+                fee_recipient: to_ssz_rs(&header.fee_recipient)?,
+                state_root: to_ssz_rs(&header.state_root)?,
+                receipts_root: to_ssz_rs(&header.receipts_root)?,
+                logs_bloom: to_ssz_rs(&header.logs_bloom)?,
+                prev_randao: to_ssz_rs(&header.prev_randao)?,
+                block_number: header.block_number,
+                gas_limit: header.gas_limit,
+                gas_used: header.gas_used,
+                timestamp: header.timestamp,
+                extra_data: to_ssz_rs(&header.extra_data)?,
+                base_fee_per_gas: to_ssz_rs(&header.base_fee_per_gas)?,
+                block_hash: to_ssz_rs(&header.block_hash)?,
+                transactions_root: to_ssz_rs(&header.transactions_root)?,
+                withdrawals_root: Default::default(),
+            },
+            ExecutionPayloadHeader::Capella(header) => ServerPayloadHeader {
+                parent_hash: to_ssz_rs(&header.parent_hash)?,
+                fee_recipient: to_ssz_rs(&header.fee_recipient)?,
+                state_root: to_ssz_rs(&header.state_root)?,
+                receipts_root: to_ssz_rs(&header.receipts_root)?,
+                logs_bloom: to_ssz_rs(&header.logs_bloom)?,
+                prev_randao: to_ssz_rs(&header.prev_randao)?,
+                block_number: header.block_number,
+                gas_limit: header.gas_limit,
+                gas_used: header.gas_used,
+                timestamp: header.timestamp,
+                extra_data: to_ssz_rs(&header.extra_data)?,
+                base_fee_per_gas: to_ssz_rs(&header.base_fee_per_gas)?,
+                block_hash: to_ssz_rs(&header.block_hash)?,
+                transactions_root: to_ssz_rs(&header.transactions_root)?,
+                withdrawals_root: to_ssz_rs(&header.withdrawals_root)?,
+            },
+            _ => {
+                return Err(convert_err(
+                    "mock builder does not support this fork for builder payload headers",
+                ))
+            }
+        })
+    }
+}
+
+impl<E: EthSpec> ToServerPayload<ServerPayload> for ExecutionPayload<E> {
+    fn to_server_payload(&self) -> Result<ServerPayload, Error> {
+        Ok(match self {
+            ExecutionPayload::Merge(payload) => ServerPayload {
+                parent_hash: to_ssz_rs(&payload.parent_hash)?,
+                fee_recipient: to_ssz_rs(&payload.fee_recipient)?,
+                state_root: to_ssz_rs(&payload.state_root)?,
+                receipts_root: to_ssz_rs(&payload.receipts_root)?,
+                logs_bloom: to_ssz_rs(&payload.logs_bloom)?,
+                prev_randao: to_ssz_rs(&payload.prev_randao)?,
+                block_number: payload.block_number,
+                gas_limit: payload.gas_limit,
+                gas_used: payload.gas_used,
+                timestamp: payload.timestamp,
+                extra_data: to_ssz_rs(&payload.extra_data)?,
+                base_fee_per_gas: to_ssz_rs(&payload.base_fee_per_gas)?,
+                block_hash: to_ssz_rs(&payload.block_hash)?,
+                transactions: to_ssz_rs(&payload.transactions)?,
+                withdrawals: Default::default(),
+            },
+            ExecutionPayload::Capella(payload) => ServerPayload {
+                parent_hash: to_ssz_rs(&payload.parent_hash)?,
+                fee_recipient: to_ssz_rs(&payload.fee_recipient)?,
+                state_root: to_ssz_rs(&payload.state_root)?,
+                receipts_root: to_ssz_rs(&payload.receipts_root)?,
+                logs_bloom: to_ssz_rs(&payload.logs_bloom)?,
+                prev_randao: to_ssz_rs(&payload.prev_randao)?,
+                block_number: payload.block_number,
+                gas_limit: payload.gas_limit,
+                gas_used: payload.gas_used,
+                timestamp: payload.timestamp,
+                extra_data: to_ssz_rs(&payload.extra_data)?,
+                base_fee_per_gas: to_ssz_rs(&payload.base_fee_per_gas)?,
+                block_hash: to_ssz_rs(&payload.block_hash)?,
+                transactions: to_ssz_rs(&payload.transactions)?,
+                withdrawals: to_ssz_rs(&payload.withdrawals)?,
+            },
+            _ => {
+                return Err(convert_err(
+                    "mock builder does not support this fork for builder payloads",
+                ))
+            }
+        })
     }
 }
 
@@ -334,3 +755,86 @@ pub fn to_ssz_rs<T: Encode, U: SimpleSerialize>(ssz_data: &T) -> Result<U, Error
 fn convert_err<E: Debug>(e: E) -> Error {
     Error::Custom(format!("{e:?}"))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fee_history_next_base_fee_is_none_when_empty() {
+        let history = FeeHistory::default();
+        assert_eq!(history.next_base_fee(), None);
+    }
+
+    #[test]
+    fn fee_history_holds_base_fee_steady_at_target_gas_usage() {
+        let mut history = FeeHistory::default();
+        history.push(Uint256::from(1_000_000_000u64), 15_000_000, 30_000_000);
+        assert_eq!(
+            history.next_base_fee(),
+            Some(Uint256::from(1_000_000_000u64))
+        );
+    }
+
+    #[test]
+    fn fee_history_raises_base_fee_when_gas_used_exceeds_target() {
+        let mut history = FeeHistory::default();
+        history.push(Uint256::from(1_000_000_000u64), 30_000_000, 30_000_000);
+        let next = history.next_base_fee().expect("non-empty history");
+        assert!(next > Uint256::from(1_000_000_000u64));
+    }
+
+    #[test]
+    fn fee_history_lowers_base_fee_when_gas_used_below_target() {
+        let mut history = FeeHistory::default();
+        history.push(Uint256::from(1_000_000_000u64), 0, 30_000_000);
+        let next = history.next_base_fee().expect("non-empty history");
+        assert!(next < Uint256::from(1_000_000_000u64));
+    }
+
+    #[test]
+    fn fee_history_next_base_fee_does_not_panic_near_u128_max() {
+        let mut history = FeeHistory::default();
+        history.push(Uint256::from(u128::MAX), 30_000_000, 30_000_000);
+        assert!(history.next_base_fee().is_some());
+    }
+
+    fn dummy_registration() -> SignedValidatorRegistration {
+        let context = Context::for_mainnet();
+        let sk = SecretKey::random(&mut rand::thread_rng()).unwrap();
+        let mut message = mev_build_rs::ValidatorRegistration {
+            fee_recipient: to_ssz_rs(&Address::repeat_byte(0x42)).unwrap(),
+            gas_limit: 30_000_000,
+            timestamp: 1_700_000_000,
+            public_key: sk.public_key(),
+        };
+        let signature = sign_builder_message(&mut message, &sk, &context).unwrap();
+        SignedValidatorRegistration { message, signature }
+    }
+
+    #[test]
+    fn registration_entry_round_trips_through_json() {
+        let registration = dummy_registration();
+        let entry = RegistrationEntry::new(&registration).expect("registration serializes");
+        let serialized = serde_json::to_string(&entry).expect("entry serializes");
+        let deserialized: RegistrationEntry =
+            serde_json::from_str(&serialized).expect("entry deserializes");
+
+        let round_tripped = deserialized
+            .into_registration()
+            .expect("checksum matches");
+        assert_eq!(
+            round_tripped.message.public_key,
+            registration.message.public_key
+        );
+    }
+
+    #[test]
+    fn registration_entry_rejects_checksum_mismatch() {
+        let registration = dummy_registration();
+        let mut entry = RegistrationEntry::new(&registration).expect("registration serializes");
+        entry.checksum = "deadbeef".to_string();
+
+        assert!(entry.into_registration().is_err());
+    }
+}